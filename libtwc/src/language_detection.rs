@@ -0,0 +1,62 @@
+use whatlang::{detect, Lang};
+
+/// Default confidence a `whatlang` detection must clear before it is
+/// trusted to override a tweet's stated language.
+pub const DEFAULT_CONFIDENCE_THRESHOLD: f64 = 0.8;
+
+/// Runs script/trigram-based language detection on `text` and, if the
+/// detector is confident enough, returns the detected language as a
+/// Twitter-style BCP-47/ISO 639-1 code.
+///
+/// `whatlang` reports ISO 639-3 codes, so the result is passed through
+/// [`iso639_3_to_twitter`] to land in the same bucket Twitter would have
+/// used, and unmapped languages are dropped rather than introducing a
+/// stray code into the corpus.
+pub fn detect_language(text: &str, confidence_threshold: f64) -> Option<&'static str> {
+    let info = detect(text)?;
+    if info.confidence() < confidence_threshold {
+        return None;
+    }
+    iso639_3_to_twitter(info.lang())
+}
+
+/// Maps the subset of `whatlang`'s ISO 639-3 languages we expect to see on
+/// Twitter to the ISO 639-1/BCP-47 codes Twitter itself tags tweets with.
+/// Languages with no common two-letter code fall through to `None`.
+fn iso639_3_to_twitter(lang: Lang) -> Option<&'static str> {
+    use Lang::*;
+    Some(match lang {
+        Eng => "en",
+        Deu => "de",
+        Fra => "fr",
+        Spa => "es",
+        Por => "pt",
+        Ita => "it",
+        Nld => "nl",
+        Pol => "pl",
+        Rus => "ru",
+        Ukr => "uk",
+        Tur => "tr",
+        Arb => "ar",
+        Heb => "he",
+        Vie => "vi",
+        Ind => "id",
+        Tha => "th",
+        Lao => "lo",
+        Khm => "km",
+        Jpn => "ja",
+        Kor => "ko",
+        Cmn => "zh",
+        Hin => "hi",
+        Ben => "bn",
+        Ell => "el",
+        Swe => "sv",
+        Fin => "fi",
+        Dan => "da",
+        Nob => "no",
+        Ces => "cs",
+        Ron => "ro",
+        Hun => "hu",
+        _ => return None,
+    })
+}
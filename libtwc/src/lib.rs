@@ -5,9 +5,15 @@ use std::collections::HashMap;
 pub type WordMap = HashMap<String, u64>;
 pub type LanguageMap = HashMap<String, WordMap>;
 
+mod language_detection;
+mod space_saving;
+mod spelling;
+mod tokenizer;
 mod tweet;
 mod tweet_compiler;
 mod util;
 
+pub use tokenizer::{LanguageTokenizers, Tokenizer};
 pub use tweet::Tweet;
 pub use tweet_compiler::TweetCompiler;
+pub use util::CasePolicy;
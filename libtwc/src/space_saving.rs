@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy)]
+struct Counter {
+    count: u64,
+    error: u64,
+}
+
+/// Fixed-capacity approximate frequency counter (the Space-Saving
+/// algorithm). Tracks at most `capacity` words, so memory stays O(capacity)
+/// regardless of how many distinct words are observed -- unlike an exact
+/// `HashMap<String, u64>`, which holds the entire long tail of a corpus
+/// until the final purge.
+///
+/// On a miss when the table is full, the least-frequent tracked word is
+/// evicted and replaced by the new word, whose count is seeded at
+/// `min_count + 1` with an error bound of `min_count`. A word's true count
+/// is therefore somewhere in `[count - error, count]`.
+#[derive(Debug)]
+pub struct SpaceSaving {
+    capacity: usize,
+    counters: HashMap<String, Counter>,
+}
+
+impl SpaceSaving {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            counters: HashMap::with_capacity(capacity),
+        }
+    }
+
+    pub fn observe(&mut self, word: String) {
+        self.merge_counter(word, Counter { count: 1, error: 0 });
+    }
+
+    /// Folds another sketch's counters into this one, as if every word it
+    /// observed had instead been observed here directly. Used to merge a
+    /// per-file local sketch into the shared per-language one, the same way
+    /// [`TweetCompiler::process_tweets`](crate::tweet_compiler::TweetCompiler)
+    /// merges its per-file exact `HashMap`s.
+    pub fn merge(&mut self, other: Self) {
+        for (word, counter) in other.counters {
+            self.merge_counter(word, counter);
+        }
+    }
+
+    fn merge_counter(&mut self, word: String, incoming: Counter) {
+        if let Some(counter) = self.counters.get_mut(&word) {
+            counter.count += incoming.count;
+            counter.error += incoming.error;
+            return;
+        }
+
+        if self.counters.len() < self.capacity {
+            self.counters.insert(word, incoming);
+            return;
+        }
+
+        let min_word = self
+            .counters
+            .iter()
+            .min_by_key(|(_, counter)| counter.count)
+            .map(|(word, _)| word.clone())
+            .expect("capacity is checked to be nonzero by callers");
+        let min_counter = self.counters.remove(&min_word).expect("min_word was just looked up");
+        self.counters.insert(
+            word,
+            Counter {
+                count: min_counter.count + incoming.count,
+                error: min_counter.count + incoming.error,
+            },
+        );
+    }
+
+    /// Words whose guaranteed minimum frequency (`count - error`) clears
+    /// `threshold`, paired with their (possibly overestimated) count.
+    pub fn heavy_hitters(&self, threshold: u64) -> HashMap<String, u64> {
+        self.counters
+            .iter()
+            .filter(|(_, counter)| counter.count - counter.error >= threshold)
+            .map(|(word, counter)| (word.clone(), counter.count))
+            .collect()
+    }
+}
@@ -0,0 +1,39 @@
+use std::{collections::HashSet, fs, path::Path};
+
+use parking_lot::RwLock;
+
+/// A loaded Hunspell `.dic`/`.aff` pair for one language, used to drop
+/// misspellings and slang fragments that the structural `word_qualifies`
+/// checks let through.
+///
+/// Affix expansion is expensive, so accepted words are cached in a
+/// `HashSet` behind an `RwLock` shared across the parallel file workers.
+pub struct SpellingDictionary {
+    checker: zspell::Dictionary,
+    accepted_cache: RwLock<HashSet<String>>,
+}
+
+impl SpellingDictionary {
+    pub fn load(aff_path: impl AsRef<Path>, dic_path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let aff = fs::read_to_string(aff_path)?;
+        let dic = fs::read_to_string(dic_path)?;
+        let checker = zspell::builder().config_str(&aff).dict_str(&dic).build()?;
+        Ok(Self {
+            checker,
+            accepted_cache: RwLock::new(HashSet::new()),
+        })
+    }
+
+    /// Whether `word`, or an affix-stripped form of it, is recognized by
+    /// the dictionary.
+    pub fn accepts(&self, word: &str) -> bool {
+        if self.accepted_cache.read().contains(word) {
+            return true;
+        }
+        let accepted = self.checker.check(word);
+        if accepted {
+            self.accepted_cache.write().insert(word.to_owned());
+        }
+        accepted
+    }
+}
@@ -0,0 +1,166 @@
+use std::collections::HashSet;
+
+use lindera::{
+    dictionary::{DictionaryConfig, DictionaryKind},
+    mode::Mode,
+    tokenizer::{Tokenizer as LinderaTokenizerImpl, TokenizerConfig},
+};
+
+/// Splits raw tweet text into surface-form tokens.
+///
+/// Space-delimited languages can just split on whitespace, but scripts
+/// written without spaces between words (Chinese, Japanese, Korean, Thai,
+/// Lao, Khmer) need a dictionary or segmenter to recover word boundaries.
+pub trait Tokenizer: Send + Sync {
+    fn tokenize(&self, text: &str) -> Vec<String>;
+}
+
+/// The tokenizer used for space-delimited languages, and the fallback for
+/// any language code we don't have a dedicated segmenter for.
+#[derive(Debug, Default)]
+pub struct WhitespaceTokenizer;
+
+impl Tokenizer for WhitespaceTokenizer {
+    fn tokenize(&self, text: &str) -> Vec<String> {
+        text.split(' ').map(str::to_string).collect()
+    }
+}
+
+/// Dictionary-based segmenter backed by `lindera`, used for CJK languages.
+pub struct LinderaTokenizer {
+    inner: LinderaTokenizerImpl,
+}
+
+impl LinderaTokenizer {
+    pub fn ipadic() -> anyhow::Result<Self> {
+        Self::with_dictionary(DictionaryKind::IPADIC)
+    }
+
+    pub fn ko_dic() -> anyhow::Result<Self> {
+        Self::with_dictionary(DictionaryKind::KoDic)
+    }
+
+    pub fn cc_cedict() -> anyhow::Result<Self> {
+        Self::with_dictionary(DictionaryKind::CcCedict)
+    }
+
+    fn with_dictionary(kind: DictionaryKind) -> anyhow::Result<Self> {
+        let config = TokenizerConfig {
+            dictionary: DictionaryConfig {
+                kind: Some(kind),
+                path: None,
+            },
+            user_dictionary: None,
+            mode: Mode::Normal,
+        };
+        Ok(Self {
+            inner: LinderaTokenizerImpl::from_config(config)?,
+        })
+    }
+}
+
+impl Tokenizer for LinderaTokenizer {
+    fn tokenize(&self, text: &str) -> Vec<String> {
+        self.inner
+            .tokenize(text)
+            .map(|tokens| tokens.into_iter().map(|token| token.text.into_owned()).collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Greedy longest-match segmenter for scripts that lindera doesn't cover
+/// here (Thai, Lao, Khmer). Given a word list, scans left to right and at
+/// each position consumes the longest dictionary entry that matches;
+/// codepoints that match nothing are emitted as single-character tokens.
+pub struct MaxMatchTokenizer {
+    words: HashSet<String>,
+    max_word_len: usize,
+}
+
+impl MaxMatchTokenizer {
+    pub fn new(words: impl IntoIterator<Item = String>) -> Self {
+        let words: HashSet<String> = words.into_iter().collect();
+        let max_word_len = words.iter().map(|word| word.chars().count()).max().unwrap_or(1);
+        Self { words, max_word_len }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+}
+
+impl Tokenizer for MaxMatchTokenizer {
+    fn tokenize(&self, text: &str) -> Vec<String> {
+        let chars = text.chars().collect::<Vec<_>>();
+        let mut tokens = Vec::new();
+
+        let mut start = 0;
+        while start < chars.len() {
+            let max_len = self.max_word_len.min(chars.len() - start);
+            let matched = (1..=max_len)
+                .rev()
+                .map(|len| chars[start..start + len].iter().collect::<String>())
+                .find(|candidate| self.words.contains(candidate));
+
+            match matched {
+                Some(word) => {
+                    start += word.chars().count();
+                    tokens.push(word);
+                }
+                None => {
+                    tokens.push(chars[start].to_string());
+                    start += 1;
+                }
+            }
+        }
+
+        tokens
+    }
+}
+
+/// Holds the per-language segmenters and routes a language code to the
+/// right one, falling back to whitespace splitting for space-delimited
+/// and unrecognized languages.
+pub struct LanguageTokenizers {
+    japanese: Option<LinderaTokenizer>,
+    korean: Option<LinderaTokenizer>,
+    chinese: Option<LinderaTokenizer>,
+    thai_like: Option<MaxMatchTokenizer>,
+    whitespace: WhitespaceTokenizer,
+}
+
+impl LanguageTokenizers {
+    /// Builds the CJK dictionaries (logging and falling back to whitespace
+    /// splitting if a dictionary fails to load) and wires up the given
+    /// Thai/Lao/Khmer word list for maximum-matching segmentation. An empty
+    /// word list falls back to whitespace splitting too, same as a CJK
+    /// dictionary that failed to load: a `MaxMatchTokenizer` with no words
+    /// can only ever emit single codepoints, which is worse than not
+    /// segmenting at all.
+    pub fn new(thai_like_words: impl IntoIterator<Item = String>) -> Self {
+        let thai_like = MaxMatchTokenizer::new(thai_like_words);
+        Self {
+            japanese: LinderaTokenizer::ipadic().ok(),
+            korean: LinderaTokenizer::ko_dic().ok(),
+            chinese: LinderaTokenizer::cc_cedict().ok(),
+            thai_like: if thai_like.is_empty() { None } else { Some(thai_like) },
+            whitespace: WhitespaceTokenizer,
+        }
+    }
+
+    pub fn tokenizer_for(&self, lang: &str) -> &dyn Tokenizer {
+        match lang {
+            "ja" if self.japanese.is_some() => self.japanese.as_ref().unwrap(),
+            "ko" if self.korean.is_some() => self.korean.as_ref().unwrap(),
+            "zh" if self.chinese.is_some() => self.chinese.as_ref().unwrap(),
+            "th" | "lo" | "km" if self.thai_like.is_some() => self.thai_like.as_ref().unwrap(),
+            _ => &self.whitespace,
+        }
+    }
+}
+
+impl Default for LanguageTokenizers {
+    fn default() -> Self {
+        Self::new(std::iter::empty())
+    }
+}
@@ -0,0 +1,8 @@
+use serde::Deserialize;
+
+/// A single tweet as read from the line-delimited JSON crawl files.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Tweet {
+    pub lang: String,
+    pub text: String,
+}
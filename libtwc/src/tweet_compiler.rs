@@ -1,4 +1,5 @@
 use std::{
+    collections::HashMap,
     path::{Path, PathBuf},
     sync::Arc,
 };
@@ -7,12 +8,62 @@ use parking_lot::{Mutex, RwLock};
 use rayon::prelude::*;
 use walkdir::WalkDir;
 
-use crate::{tweet::Tweet, util, LanguageMap};
+use crate::{
+    language_detection::{self, DEFAULT_CONFIDENCE_THRESHOLD},
+    space_saving::SpaceSaving,
+    spelling::SpellingDictionary,
+    tokenizer::LanguageTokenizers,
+    tweet::Tweet,
+    util::{self, CasePolicy},
+    LanguageMap,
+};
+
+/// Default for [`TweetCompiler::with_minimum_frequency`]: words seen fewer
+/// than this many times across the whole corpus are dropped, whether by
+/// the exact-map purge or as a Space-Saving heavy-hitter threshold.
+const DEFAULT_MINIMUM_FREQUENCY: u64 = 100;
 
-#[derive(Debug, Default)]
 pub struct TweetCompiler {
     files: Vec<PathBuf>,
     language_map: RwLock<LanguageMap>,
+    tokenizers: Arc<LanguageTokenizers>,
+    detect_language: bool,
+    detection_confidence_threshold: f64,
+    spelling_dictionaries: Arc<HashMap<String, SpellingDictionary>>,
+    case_policy: CasePolicy,
+    sketch_capacity: Option<usize>,
+    minimum_frequency: u64,
+}
+
+impl Default for TweetCompiler {
+    fn default() -> Self {
+        Self {
+            files: Default::default(),
+            language_map: Default::default(),
+            tokenizers: Arc::new(LanguageTokenizers::default()),
+            detect_language: false,
+            detection_confidence_threshold: DEFAULT_CONFIDENCE_THRESHOLD,
+            spelling_dictionaries: Default::default(),
+            case_policy: CasePolicy::default(),
+            sketch_capacity: None,
+            minimum_frequency: DEFAULT_MINIMUM_FREQUENCY,
+        }
+    }
+}
+
+impl std::fmt::Debug for TweetCompiler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TweetCompiler")
+            .field("files", &self.files)
+            .field("language_map", &self.language_map)
+            .field("detect_language", &self.detect_language)
+            .field("detection_confidence_threshold", &self.detection_confidence_threshold)
+            .field("spelling_dictionaries", &self.spelling_dictionaries.keys().collect::<Vec<_>>())
+            .field("case_policy", &self.case_policy)
+            .field("sketch_capacity", &self.sketch_capacity)
+            .field("minimum_frequency", &self.minimum_frequency)
+            .finish_non_exhaustive()
+    }
 }
 
 impl TweetCompiler {
@@ -42,15 +93,173 @@ impl TweetCompiler {
         }
     }
 
+    /// Supplies the word list used to maximum-match segment Thai, Lao, and
+    /// Khmer tweets, which are otherwise written without spaces between
+    /// words. Without this, those languages fall back to single-codepoint
+    /// tokens.
+    pub fn with_thai_like_dictionary(mut self, words: impl IntoIterator<Item = String>) -> Self {
+        self.tokenizers = Arc::new(LanguageTokenizers::new(words));
+        self
+    }
+
+    /// Re-detects each tweet's language from its text instead of trusting
+    /// Twitter's `lang` field outright, which is frequently wrong, missing,
+    /// or `und`. A tweet is reassigned to the detected language when
+    /// detection clears [`Self::with_detection_confidence_threshold`] and
+    /// disagrees with `lang`, or unconditionally when `lang` is empty or
+    /// `und`.
+    pub fn with_language_detection(mut self, enabled: bool) -> Self {
+        self.detect_language = enabled;
+        self
+    }
+
+    /// Sets the minimum `whatlang` confidence required before a detected
+    /// language is trusted to override a tweet's stated `lang`. Defaults to
+    /// [`DEFAULT_CONFIDENCE_THRESHOLD`].
+    pub fn with_detection_confidence_threshold(mut self, threshold: f64) -> Self {
+        self.detection_confidence_threshold = threshold;
+        self
+    }
+
+    /// Loads a Hunspell `.dic`/`.aff` pair and uses it to additionally
+    /// require that a word is spellchecker-recognized before it's kept, on
+    /// top of the existing frequency purge. `lang` must be the same bare
+    /// BCP-47/ISO 639-1 code tweets carry (e.g. `en`, `de`), since that's
+    /// what [`extract_words`](Self::extract_words) looks dictionaries up
+    /// by -- not a locale-qualified Hunspell dictionary name like `en_US`.
+    /// Languages with no dictionary supplied are left unvalidated, so
+    /// behavior is unchanged by default.
+    pub fn with_spelling_dictionary(
+        mut self,
+        lang: impl Into<String>,
+        aff_path: impl AsRef<Path>,
+        dic_path: impl AsRef<Path>,
+    ) -> anyhow::Result<Self> {
+        let dictionary = SpellingDictionary::load(aff_path, dic_path)?;
+        Arc::get_mut(&mut self.spelling_dictionaries)
+            .expect("spelling_dictionaries is not shared before compile()")
+            .insert(lang.into(), dictionary);
+        Ok(self)
+    }
+
+    /// Sets whether [`util::cleanup_word`] lowercases words. Defaults to
+    /// [`CasePolicy::FoldCase`].
+    pub fn with_case_policy(mut self, case_policy: CasePolicy) -> Self {
+        self.case_policy = case_policy;
+        self
+    }
+
+    /// Switches to a streaming mode that replaces each per-language exact
+    /// `HashMap<String, u64>` with a fixed-capacity Space-Saving sketch
+    /// tracking at most `capacity` words, capping memory at O(capacity) per
+    /// language regardless of corpus size. This replaces the final
+    /// below-[`MIN_WORD_FREQUENCY`] purge with a heavy-hitters pass over
+    /// the sketch; disabled by default in favor of the exact `HashMap`
+    /// path, which is cheaper for corpora that comfortably fit in memory.
+    pub fn with_streaming_heavy_hitters(mut self, capacity: usize) -> Self {
+        assert!(capacity > 0, "streaming heavy-hitters capacity must be nonzero");
+        self.sketch_capacity = Some(capacity);
+        self
+    }
+
+    /// Sets the minimum corpus-wide frequency a word must reach to survive
+    /// the final purge (exact mode) or heavy-hitters pass (streaming mode).
+    /// Defaults to [`DEFAULT_MINIMUM_FREQUENCY`].
+    pub fn with_minimum_frequency(mut self, threshold: u64) -> Self {
+        self.minimum_frequency = threshold;
+        self
+    }
+
     pub fn compile(self) -> LanguageMap {
-        let language_map_shared = Arc::new(&self.language_map);
+        match self.sketch_capacity {
+            Some(capacity) => self.compile_streaming(capacity),
+            None => self.compile_exact(),
+        }
+    }
 
-        // Parallelly loop over all input files
-        let file_count = self.files.len();
-        let processed_count = Arc::new(Mutex::new(0_usize));
-        self.files.into_par_iter().for_each(|file| {
-            let language_map = language_map_shared.clone();
+    fn compile_exact(self) -> LanguageMap {
+        let TweetCompiler {
+            files,
+            language_map,
+            tokenizers,
+            detect_language,
+            detection_confidence_threshold,
+            spelling_dictionaries,
+            case_policy,
+            minimum_frequency,
+            ..
+        } = self;
+
+        Self::for_each_file(files, |tweets| {
+            Self::process_tweets(
+                tweets,
+                &language_map,
+                &tokenizers,
+                detect_language,
+                detection_confidence_threshold,
+                &spelling_dictionaries,
+                case_policy,
+            );
+        });
 
+        // Purge infrequently used words
+        for word_map in (*language_map.write()).values_mut() {
+            let infrequent_word_pairs = word_map
+                .iter()
+                .filter(|(_, &count)| count < minimum_frequency)
+                .map(|(key, _)| key.to_owned())
+                .collect::<Vec<_>>();
+            for key in infrequent_word_pairs {
+                word_map.remove(&key);
+            }
+        }
+
+        language_map.into_inner()
+    }
+
+    fn compile_streaming(self, capacity: usize) -> LanguageMap {
+        let TweetCompiler {
+            files,
+            tokenizers,
+            detect_language,
+            detection_confidence_threshold,
+            spelling_dictionaries,
+            case_policy,
+            minimum_frequency,
+            ..
+        } = self;
+
+        let sketches: RwLock<HashMap<String, SpaceSaving>> = RwLock::new(HashMap::new());
+
+        Self::for_each_file(files, |tweets| {
+            Self::process_tweets_streaming(
+                tweets,
+                &sketches,
+                capacity,
+                &tokenizers,
+                detect_language,
+                detection_confidence_threshold,
+                &spelling_dictionaries,
+                case_policy,
+            );
+        });
+
+        sketches
+            .into_inner()
+            .into_iter()
+            .map(|(language, sketch)| (language, sketch.heavy_hitters(minimum_frequency)))
+            .collect()
+    }
+}
+
+// Helper methods
+impl TweetCompiler {
+    /// Parallelly loops over all input files, printing progress, and hands
+    /// each file's parsed tweets to `on_tweets`.
+    fn for_each_file(files: Vec<PathBuf>, on_tweets: impl Fn(Vec<Tweet>) + Sync) {
+        let file_count = files.len();
+        let processed_count = Mutex::new(0_usize);
+        files.into_par_iter().for_each(|file| {
             // Print progress
             let processed_count = {
                 let mut processed_count = processed_count.lock();
@@ -68,39 +277,66 @@ impl TweetCompiler {
 
             // Parse file and process tweets
             if let Ok(tweets) = util::read_file(file) {
-                Self::process_tweets(tweets, *language_map);
+                on_tweets(tweets);
             }
         });
+    }
 
-        // Purge infrequently used words
-        for word_map in (*self.language_map.write()).values_mut() {
-            let infrequent_word_pairs = word_map
-                .iter()
-                .filter(|(_, &count)| count < 100_u64)
-                .map(|(key, _)| key.to_owned())
-                .collect::<Vec<_>>();
-            for key in infrequent_word_pairs {
-                word_map.remove(&key);
+    /// Re-detects language (if enabled), tokenizes, cleans up, and filters
+    /// a tweet's text, returning its (possibly reassigned) language and the
+    /// qualifying words.
+    fn extract_words(
+        mut tweet: Tweet,
+        tokenizers: &LanguageTokenizers,
+        detect_language: bool,
+        detection_confidence_threshold: f64,
+        spelling_dictionaries: &HashMap<String, SpellingDictionary>,
+        case_policy: CasePolicy,
+    ) -> (String, Vec<String>) {
+        if detect_language {
+            let is_untrustworthy = tweet.lang.is_empty() || tweet.lang == "und";
+            let detected = language_detection::detect_language(&tweet.text, detection_confidence_threshold)
+                .filter(|&detected| is_untrustworthy || detected != tweet.lang);
+            if let Some(detected) = detected {
+                tweet.lang = detected.to_owned();
             }
         }
 
-        self.language_map.into_inner()
+        let tokenizer = tokenizers.tokenizer_for(&tweet.lang);
+        let dictionary = spelling_dictionaries.get(&tweet.lang);
+        let words = tokenizer
+            .tokenize(&tweet.text)
+            .into_iter()
+            .map(|word| util::cleanup_word(word, &tweet.lang, case_policy))
+            .filter(util::word_qualifies)
+            .filter(|word| dictionary.map(|dictionary| dictionary.accepts(word)).unwrap_or(true))
+            .collect();
+
+        (tweet.lang, words)
     }
-}
 
-// Helper methods
-impl TweetCompiler {
-    fn process_tweets(tweets: Vec<Tweet>, global_map: &RwLock<LanguageMap>) {
+    fn process_tweets(
+        tweets: Vec<Tweet>,
+        global_map: &RwLock<LanguageMap>,
+        tokenizers: &LanguageTokenizers,
+        detect_language: bool,
+        detection_confidence_threshold: f64,
+        spelling_dictionaries: &HashMap<String, SpellingDictionary>,
+        case_policy: CasePolicy,
+    ) {
         let mut local_map = LanguageMap::new();
 
         // Group words by language
         for tweet in tweets {
-            let language_entry = local_map.entry(tweet.lang).or_default();
-            let words = tweet
-                .text
-                .split(' ')
-                .map(util::cleanup_word)
-                .filter(util::word_qualifies);
+            let (lang, words) = Self::extract_words(
+                tweet,
+                tokenizers,
+                detect_language,
+                detection_confidence_threshold,
+                spelling_dictionaries,
+                case_policy,
+            );
+            let language_entry = local_map.entry(lang).or_default();
             for word in words {
                 *language_entry.entry(word).or_default() += 1;
             }
@@ -115,4 +351,41 @@ impl TweetCompiler {
             }
         }
     }
+
+    fn process_tweets_streaming(
+        tweets: Vec<Tweet>,
+        sketches: &RwLock<HashMap<String, SpaceSaving>>,
+        capacity: usize,
+        tokenizers: &LanguageTokenizers,
+        detect_language: bool,
+        detection_confidence_threshold: f64,
+        spelling_dictionaries: &HashMap<String, SpellingDictionary>,
+        case_policy: CasePolicy,
+    ) {
+        let mut local_sketches: HashMap<String, SpaceSaving> = HashMap::new();
+
+        for tweet in tweets {
+            let (lang, words) = Self::extract_words(
+                tweet,
+                tokenizers,
+                detect_language,
+                detection_confidence_threshold,
+                spelling_dictionaries,
+                case_policy,
+            );
+            let sketch = local_sketches.entry(lang).or_insert_with(|| SpaceSaving::new(capacity));
+            for word in words {
+                sketch.observe(word);
+            }
+        }
+
+        // Merge results into the shared sketches
+        let mut sketches = sketches.write();
+        for (language, local_sketch) in local_sketches {
+            sketches
+                .entry(language)
+                .or_insert_with(|| SpaceSaving::new(capacity))
+                .merge(local_sketch);
+        }
+    }
 }
@@ -1,9 +1,30 @@
 use std::{fs::OpenOptions, io::Read, ops::RangeInclusive, path::Path};
 
 use bzip2::read::BzDecoder;
+use unicode_normalization::UnicodeNormalization;
 
 use crate::tweet::Tweet;
 
+/// Languages where naively lowercasing corrupts words: German capitalizes
+/// all nouns, and Turkish's dotted/dotless I pair doesn't round-trip
+/// through ASCII-locale lowercasing.
+const CASE_SENSITIVE_LANGUAGES: [&str; 2] = ["de", "tr"];
+
+/// Whether [`cleanup_word`] lowercases its output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CasePolicy {
+    /// Lowercase words, except in [`CASE_SENSITIVE_LANGUAGES`].
+    FoldCase,
+    /// Leave case untouched.
+    PreserveCase,
+}
+
+impl Default for CasePolicy {
+    fn default() -> Self {
+        Self::FoldCase
+    }
+}
+
 pub fn read_file(filename: impl AsRef<Path>) -> anyhow::Result<Vec<Tweet>> {
     let contents = {
         let file = OpenOptions::new().read(true).open(filename)?;
@@ -18,24 +39,42 @@ pub fn read_file(filename: impl AsRef<Path>) -> anyhow::Result<Vec<Tweet>> {
         .collect())
 }
 
-pub fn cleanup_word(word: impl AsRef<str>) -> String {
+/// Trims structural junk and folds a word into a canonical surface form.
+///
+/// NFKC normalization is applied first, so full-width Latin (`ＨＥＬＬＯ`)
+/// and styled Unicode (math/sans-serif `𝐇𝐞𝐥𝐥𝐨`) collapse onto the same
+/// token as their plain ASCII form before the frequency count ever sees
+/// them. `case_policy` then optionally lowercases the result, skipping
+/// `lang`s where that would corrupt the word (see [`CasePolicy`]).
+pub fn cleanup_word(word: impl AsRef<str>, lang: &str, case_policy: CasePolicy) -> String {
     const QUOTATION_MARKS: &str = "„“‟”‟’’❝❞〝〞〟＂'‚‘❛❜`\"";
     const SYMBOLS: &str = "!$%^&*()_-+=<,>.?/{}[]\\|~\t\r\n";
-    word.as_ref()
+
+    let normalized = word.as_ref().nfkc().collect::<String>();
+    let trimmed = normalized
         .trim_matches(char::is_whitespace)
         .trim_matches(&QUOTATION_MARKS.chars().collect::<Vec<_>>()[..])
-        .trim_matches(&SYMBOLS.chars().collect::<Vec<_>>()[..])
-        .to_string()
+        .trim_matches(&SYMBOLS.chars().collect::<Vec<_>>()[..]);
+
+    match case_policy {
+        CasePolicy::FoldCase if !CASE_SENSITIVE_LANGUAGES.contains(&lang) => trimmed.to_lowercase(),
+        _ => trimmed.to_string(),
+    }
 }
 
 pub fn word_qualifies(word: &String) -> bool {
     use url::Url;
 
-    // Zalgo detection algorithm
+    // Zalgo detection algorithm. `word` has already been through
+    // `cleanup_word`'s NFKC normalization, which canonically composes a
+    // base character with its first combining mark (e.g. `e` + U+0301 into
+    // `é`) and would otherwise understate how many combining marks are
+    // actually stacked on a character. NFD-decomposing first undoes that
+    // composition so the ratio reflects the original mark count.
     fn is_zalgo(s: &str) -> bool {
         use zalgo::is_zalgo;
         const ZALGO_MIN_RATIO: f64 = 0.75;
-        let chars = s.chars().collect::<Vec<_>>();
+        let chars = s.nfd().collect::<Vec<_>>();
         chars.iter().filter(|&&c| is_zalgo(c)).count() as f64 / chars.len() as f64 > ZALGO_MIN_RATIO
     }
 
@@ -83,9 +122,108 @@ pub fn word_qualifies(word: &String) -> bool {
         s if is_only_symbols(s) => false,
         // Zalgo
         s if is_zalgo(s) => false,
-        // Just twitter shit
-        "RT" => false,
+        // Just twitter shit. Matched case-insensitively since cleanup_word's
+        // case folding runs before this filter and would otherwise turn
+        // "RT" into "rt" and dodge a literal match.
+        s if s.eq_ignore_ascii_case("rt") => false,
         // Normal text
         _ => true,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn word_qualifies_rejects_empty_and_single_char() {
+        assert!(!word_qualifies(&String::new()));
+        assert!(!word_qualifies(&"a".to_string()));
+    }
+
+    #[test]
+    fn word_qualifies_rejects_mentions_and_hashtags() {
+        assert!(!word_qualifies(&"@someone".to_string()));
+        assert!(!word_qualifies(&"#topic".to_string()));
+    }
+
+    #[test]
+    fn word_qualifies_rejects_urls() {
+        assert!(!word_qualifies(&"https://example.com/path".to_string()));
+        assert!(!word_qualifies(&"ftp://example.com".to_string()));
+    }
+
+    #[test]
+    fn word_qualifies_rejects_numeric_strings() {
+        assert!(!word_qualifies(&"12345".to_string()));
+    }
+
+    #[test]
+    fn word_qualifies_rejects_pure_emoji_strings() {
+        assert!(!word_qualifies(&"\u{1F600}\u{1F603}".to_string()));
+    }
+
+    #[test]
+    fn word_qualifies_accepts_mixed_emoji_and_text() {
+        assert!(word_qualifies(&"hello\u{1F600}".to_string()));
+    }
+
+    #[test]
+    fn word_qualifies_rejects_control_characters() {
+        assert!(!word_qualifies(&"\t\r\n".to_string()));
+    }
+
+    #[test]
+    fn word_qualifies_rejects_html_escapes() {
+        assert!(!word_qualifies(&"&amp;".to_string()));
+    }
+
+    #[test]
+    fn word_qualifies_rejects_symbol_only_strings() {
+        assert!(!word_qualifies(&"!?!?".to_string()));
+    }
+
+    #[test]
+    fn word_qualifies_rejects_retweet_marker_case_insensitively() {
+        assert!(!word_qualifies(&"RT".to_string()));
+        assert!(!word_qualifies(&"rt".to_string()));
+    }
+
+    #[test]
+    fn word_qualifies_rejects_zalgo_over_the_ratio() {
+        let zalgo = format!("e{}", "\u{0301}".repeat(4));
+        assert!(!word_qualifies(&zalgo));
+    }
+
+    #[test]
+    fn word_qualifies_accepts_zalgo_just_under_the_ratio() {
+        let mild_accent = format!("e{}", "\u{0301}".repeat(2));
+        assert!(word_qualifies(&mild_accent));
+    }
+
+    #[test]
+    fn word_qualifies_accepts_normal_text() {
+        assert!(word_qualifies(&"hello".to_string()));
+    }
+
+    #[test]
+    fn cleanup_word_trims_quotes_symbols_and_whitespace() {
+        assert_eq!(cleanup_word(" \"hello!\" ", "en", CasePolicy::FoldCase), "hello");
+    }
+
+    #[test]
+    fn cleanup_word_folds_fullwidth_and_styled_unicode() {
+        assert_eq!(cleanup_word("\u{FF28}\u{FF25}\u{FF2C}\u{FF2C}\u{FF2F}", "en", CasePolicy::FoldCase), "hello");
+        assert_eq!(cleanup_word("\u{1D407}\u{1D41E}\u{1D421}\u{1D421}\u{1D428}", "en", CasePolicy::FoldCase), "hello");
+    }
+
+    #[test]
+    fn cleanup_word_preserves_case_for_case_sensitive_languages() {
+        assert_eq!(cleanup_word("Haus", "de", CasePolicy::FoldCase), "Haus");
+    }
+
+    #[test]
+    fn cleanup_word_can_preserve_case_globally() {
+        assert_eq!(cleanup_word("Hello", "en", CasePolicy::PreserveCase), "Hello");
+    }
+}
@@ -0,0 +1,66 @@
+//! Golden-corpus accuracy tests: hand-labeled fixture tweets in
+//! `tests/data/*.jsonl.bz2`, asserting the end-to-end `TweetCompiler`
+//! output keeps and rejects the expected tokens.
+
+use std::path::PathBuf;
+
+use libtwc::TweetCompiler;
+use unicode_normalization::UnicodeNormalization;
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/data").join(name)
+}
+
+#[test]
+fn compiles_expected_words_from_basic_english_fixture() {
+    let language_map = TweetCompiler::new(vec![fixture("en_basic.jsonl.bz2")])
+        .with_minimum_frequency(1)
+        .compile();
+
+    let en = language_map.get("en").expect("fixture only contains English tweets");
+    assert_eq!(en.get("hello").copied(), Some(2));
+    assert_eq!(en.get("world").copied(), Some(1));
+    assert_eq!(en.get("there").copied(), Some(1));
+    assert_eq!(en.get("check").copied(), Some(1));
+    assert_eq!(en.get("this").copied(), Some(1));
+    assert_eq!(en.get("out").copied(), Some(1));
+
+    // RT marker, mentions, hashtags, and URLs never reach the word map.
+    assert!(!en.contains_key("rt"));
+    assert!(!en.contains_key("RT"));
+    assert!(!en.contains_key("@someone"));
+    assert!(!en.contains_key("#topic"));
+    assert!(!en.contains_key("https://example.com"));
+}
+
+#[test]
+fn filters_adversarial_tokens_but_keeps_mixed_emoji_and_near_threshold_zalgo() {
+    let language_map = TweetCompiler::new(vec![fixture("adversarial.jsonl.bz2")])
+        .with_minimum_frequency(1)
+        .compile();
+
+    let en = language_map.get("en").expect("fixture only contains English tweets");
+
+    // Pure emoji runs, full-width digits (folded to ASCII and then rejected
+    // as numeric), and the RT marker are all dropped.
+    assert!(!en.contains_key("\u{1F600}\u{1F603}"));
+    assert!(!en.contains_key("\u{FF11}\u{FF12}\u{FF13}"));
+    assert!(!en.contains_key("123"));
+    assert!(!en.contains_key("rt"));
+
+    // A word glued to an emoji isn't all-emoji, so it survives.
+    assert_eq!(en.get("hello\u{1F600}").copied(), Some(1));
+
+    // Zalgo just under the 0.75 combining-mark ratio survives; just over is
+    // dropped. The word map is keyed by `cleanup_word`'s output, which
+    // NFKC-normalizes first and so composes the base letter with its first
+    // combining mark (`e` + U+0301 into `é`) — build the lookup keys the
+    // same way rather than against the raw, un-normalized fixture text.
+    let mild_accent = format!("e{}", "\u{0301}".repeat(2)).nfkc().collect::<String>();
+    let heavy_accent = format!("e{}", "\u{0301}".repeat(4)).nfkc().collect::<String>();
+    assert_eq!(en.get(&mild_accent).copied(), Some(1));
+    assert!(!en.contains_key(&heavy_accent));
+
+    assert_eq!(en.get("check").copied(), Some(1));
+    assert_eq!(en.get("this").copied(), Some(1));
+}
@@ -0,0 +1,75 @@
+use std::{
+    fs::OpenOptions,
+    io::{BufWriter, Write},
+};
+
+use libtwc::WordMap;
+
+/// Serialized output format written per language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// `word count` text lines, sorted by descending count (the original format).
+    Text,
+    Json,
+    Cbor,
+    Msgpack,
+    /// Compressed finite-state transducer mapping word -> count, via the `fst` crate.
+    /// Sorted, immutable, and mmap-able without deserializing the whole corpus.
+    Fst,
+}
+
+impl OutputFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Text => "txt",
+            Self::Json => "json",
+            Self::Cbor => "cbor",
+            Self::Msgpack => "msgpack",
+            Self::Fst => "fst",
+        }
+    }
+}
+
+pub fn write_word_list(format: OutputFormat, language: &str, word_list: &WordMap) -> anyhow::Result<()> {
+    let filename = format!("output/twitter_corpus_{}.{}", language, format.extension());
+    let file = OpenOptions::new().create(true).truncate(true).write(true).open(filename)?;
+    let mut writer = BufWriter::new(file);
+
+    match format {
+        OutputFormat::Text => write_text(&mut writer, word_list)?,
+        OutputFormat::Json => serde_json::to_writer(&mut writer, word_list)?,
+        OutputFormat::Cbor => ciborium::into_writer(word_list, &mut writer)?,
+        OutputFormat::Msgpack => rmp_serde::encode::write(&mut writer, word_list)?,
+        OutputFormat::Fst => write_fst(writer, word_list)?,
+    }
+
+    Ok(())
+}
+
+fn write_text(mut writer: impl Write, word_list: &WordMap) -> anyhow::Result<()> {
+    let entries = {
+        let mut kvps = Vec::from_iter(word_list);
+        kvps.sort_by(|&(_, a), &(_, b)| b.cmp(a));
+        kvps
+    };
+    for (word, count) in entries {
+        writeln!(writer, "{} {}", word, count)?;
+    }
+    Ok(())
+}
+
+fn write_fst(writer: impl Write, word_list: &WordMap) -> anyhow::Result<()> {
+    use fst::MapBuilder;
+
+    // MapBuilder requires strictly increasing byte keys.
+    let mut entries = Vec::from_iter(word_list);
+    entries.sort_by(|&(a, _), &(b, _)| a.cmp(b));
+
+    let mut builder = MapBuilder::new(writer)?;
+    for (word, count) in entries {
+        builder.insert(word, *count)?;
+    }
+    builder.finish()?;
+
+    Ok(())
+}
@@ -1,35 +1,80 @@
-use std::{
-    fs::OpenOptions,
-    io::{BufWriter, Write},
-};
+use std::path::PathBuf;
 
 use clap::Parser;
-use libtwc::compile_word_map;
+use libtwc::TweetCompiler;
+
+mod format;
+
+use format::OutputFormat;
 
 #[derive(Debug, clap::Parser)]
-struct Args {}
+struct Args {
+    /// Directory to recursively scan for `.bz2` tweet crawl files.
+    #[arg(long, default_value = "data")]
+    input: PathBuf,
+
+    /// Serialized output format written for each language's word list.
+    #[arg(long, value_enum, default_value = "text")]
+    format: OutputFormat,
+
+    /// Re-detect each tweet's language from its text instead of trusting
+    /// Twitter's stated `lang` field outright.
+    #[arg(long)]
+    detect_language: bool,
+
+    /// Path to a newline-delimited word list used to maximum-match segment
+    /// Thai, Lao, and Khmer tweets, which are otherwise written without
+    /// spaces between words.
+    #[arg(long, value_name = "PATH")]
+    thai_like_dictionary: Option<PathBuf>,
+
+    /// Hunspell `.aff`/`.dic` pair used to additionally require that a
+    /// word is spellchecker-recognized before it's kept, given as
+    /// `LANG:AFF_PATH:DIC_PATH` (e.g. `en:en_US.aff:en_US.dic`). Repeatable.
+    #[arg(long = "spelling-dictionary", value_name = "LANG:AFF_PATH:DIC_PATH", value_parser = parse_spelling_dictionary)]
+    spelling_dictionaries: Vec<(String, PathBuf, PathBuf)>,
+
+    /// Switches to a streaming mode that tracks at most this many words per
+    /// language via a fixed-capacity Space-Saving sketch, capping memory
+    /// regardless of corpus size, at the cost of exactness. Disabled by
+    /// default in favor of the exact path.
+    #[arg(long, value_name = "CAPACITY")]
+    streaming_capacity: Option<usize>,
+}
+
+fn parse_spelling_dictionary(s: &str) -> Result<(String, PathBuf, PathBuf), String> {
+    let mut parts = s.splitn(3, ':');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(lang), Some(aff_path), Some(dic_path)) => {
+            Ok((lang.to_string(), PathBuf::from(aff_path), PathBuf::from(dic_path)))
+        }
+        _ => Err(format!("expected `LANG:AFF_PATH:DIC_PATH`, got `{s}`")),
+    }
+}
 
 fn main() -> anyhow::Result<()> {
-    let _args = Args::parse();
-    let language_map = compile_word_map()?;
+    let args = Args::parse();
+
+    let mut compiler = TweetCompiler::from_directory(&args.input).with_language_detection(args.detect_language);
+
+    if let Some(path) = &args.thai_like_dictionary {
+        let words = std::fs::read_to_string(path)?.lines().map(str::to_string).collect::<Vec<_>>();
+        compiler = compiler.with_thai_like_dictionary(words);
+    }
+
+    for (lang, aff_path, dic_path) in &args.spelling_dictionaries {
+        compiler = compiler.with_spelling_dictionary(lang.as_str(), aff_path, dic_path)?;
+    }
+
+    if let Some(capacity) = args.streaming_capacity {
+        compiler = compiler.with_streaming_heavy_hitters(capacity);
+    }
+
+    let language_map = compiler.compile();
 
     std::fs::create_dir_all("output")?;
     for (language, word_list) in language_map {
-        let filename = format!("output/twitter_corpus_{}.txt", language);
-        let entries = {
-            let mut kvps = Vec::from_iter(word_list);
-            kvps.sort_by(|&(_, a), &(_, b)| b.cmp(&a));
-            kvps
-        };
-        let file = OpenOptions::new()
-            .create(true)
-            .truncate(true)
-            .write(true)
-            .open(filename)?;
-        let mut writer = BufWriter::new(file);
-        for (word, count) in entries {
-            writeln!(writer, "{} {}", word, count)?;
-        }
+        format::write_word_list(args.format, &language, &word_list)?;
     }
 
     Ok(())